@@ -1,4 +1,6 @@
 use bracket_lib::prelude::*;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use std::io::Cursor;
 use std::sync::mpsc;
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -6,6 +8,7 @@ use std::thread;
 enum GameMode {
     Menu,
     Playing,
+    Paused,
     End,
 }
 
@@ -13,17 +16,27 @@ const SCREEN_WIDTH: i32 = 80;
 const SCREEN_HEIGHT: i32 = 50;
 const FRAME_DURATION: f32 = 75.0;
 
+const GROUND_ROW: i32 = SCREEN_HEIGHT - 1;
+const GROUND_PATTERN: [char; 4] = ['_', '=', '_', '='];
+const BACKGROUND_ROW: i32 = 5;
+const BACKGROUND_SPACING: i32 = 10;
+
 #[derive(Debug)]
 pub enum PlayerError {
     AlreadyDead,
     FallingTooFast,
 }
 
+// 玩家动画帧：拍打上升 / 下落时各自的字形序列，均取自默认 cp437 字体，无需额外精灵表
+const DRAGON_FRAMES_UP: [char; 3] = ['^', 'A', '^'];
+const DRAGON_FRAMES_DOWN: [char; 3] = ['v', 'V', 'v'];
+
 struct Player {
     x: i32,
     y: i32,
     velocity: f32,
     alive: bool,
+    frame: u16, // 当前动画帧计数
 }
 
 impl Player {
@@ -34,14 +47,17 @@ impl Player {
             y,
             velocity: 0.0,
             alive: true,
+            frame: 0,
         }
     }
 
     // 移动玩家
     pub fn try_move(&mut self) -> Option<()> {
-        if !self.alive { 
+        if !self.alive {
             return None; //死亡时不能移动
         }
+        // 推进动画帧
+        self.frame = self.frame.wrapping_add(1);
         // 重力加速度
         if self.velocity < 2.0 {
             self.velocity += 0.2;
@@ -72,12 +88,18 @@ impl Player {
         Ok(())
     }
 
-    // 渲染玩家
+    // 渲染玩家：按速度方向选择帧序列，拍打上升时朝上，下落时朝下
     pub fn render(&mut self, ctx: &mut BTerm) {
         if !self.alive {
             return;
         }
-        ctx.set(0, self.y, YELLOW, BLACK, to_cp437('@'));
+        let frames = if self.velocity < 0.0 {
+            &DRAGON_FRAMES_UP
+        } else {
+            &DRAGON_FRAMES_DOWN
+        };
+        let glyph = frames[self.frame as usize % frames.len()];
+        ctx.set(0, self.y, YELLOW, BLACK, to_cp437(glyph));
     }
 
     // 重置玩家状态
@@ -86,6 +108,7 @@ impl Player {
         self.y = y;
         self.velocity = 0.0;
         self.alive = true;
+        self.frame = 0;
     }
 
     // 获取玩家位置
@@ -109,6 +132,21 @@ struct HighScore {
     score: i32,
 }
 
+const HIGH_SCORE_FILE: &str = "highscore.dat";
+
+// 从磁盘读取历史最高分，文件缺失或损坏时视为 0
+fn load_high_score() -> i32 {
+    std::fs::read_to_string(HIGH_SCORE_FILE)
+        .ok()
+        .and_then(|contents| contents.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+// 将最高分写回磁盘，写入失败（如只读文件系统）不影响游戏运行
+fn save_high_score(score: i32) {
+    let _ = std::fs::write(HIGH_SCORE_FILE, score.to_string());
+}
+
 // 游戏主状态
 struct State {
     player: Player,
@@ -118,14 +156,22 @@ struct State {
     score: i32,
     obstacle_receiver: mpsc::Receiver<Obstacle>,
     high_score: Arc<Mutex<HighScore>>,
+    spawner_paused: Arc<Mutex<bool>>, // 通知障碍物生成线程暂停/恢复
+    shared_score: Arc<Mutex<i32>>, // 供障碍物生成线程读取当前分数以调整难度
+    audio: AudioSystem,
+    ground_offset: i32, // 地面滚动位置
+    background_offset: i32, // 远景视差滚动位置，速度是地面的一部分
 }
 
 // 游戏主循环接口
 impl GameState for State {
     fn tick(&mut self, ctx: &mut BTerm) {
-        match self.mode { // 主菜单、游戏中、结束
+        // 生成线程只应在"游戏中"运行；菜单、暂停、结束画面都要停止生成
+        *self.spawner_paused.lock().unwrap() = !matches!(self.mode, GameMode::Playing);
+
+        match self.mode { // 主菜单、游戏中、暂停、结束
             GameMode::Menu => self.main_menu(ctx),
-            GameMode::Playing => self.play(ctx),
+            GameMode::Playing | GameMode::Paused => self.play(ctx),
             GameMode::End => self.dead(ctx),
         }
     }
@@ -135,9 +181,13 @@ impl State {
     // 创建新游戏状态
     fn new() -> Self {
         let (sender, receiver) = mpsc::channel();  // 创建障碍物生成通道
-        // 初始化共享高分
-        let high_score = Arc::new(Mutex::new(HighScore { score: 0 }));
-        
+        // 初始化共享高分，启动时从磁盘加载历史记录
+        let high_score = Arc::new(Mutex::new(HighScore { score: load_high_score() }));
+        // 共享暂停标志，供障碍物生成线程检查
+        let spawner_paused = Arc::new(Mutex::new(false));
+        // 共享分数，供障碍物生成线程读取以收紧间隙
+        let shared_score = Arc::new(Mutex::new(0));
+
         let mut obstacles = Vec::new(); // 生成初始障碍物
         let mut rng = RandomNumberGenerator::new();
         let mut x = SCREEN_WIDTH;
@@ -150,12 +200,21 @@ impl State {
 
         // 创建障碍物生成线程
         let thread_x = x;
+        let thread_paused = spawner_paused.clone();
+        let thread_score = shared_score.clone();
         thread::spawn(move || {
             let mut rng = RandomNumberGenerator::new();
             let mut x = thread_x;
             loop {
+                // 暂停期间不生成也不推进障碍物位置，真正冻结世界
+                if *thread_paused.lock().unwrap() {
+                    thread::sleep(std::time::Duration::from_millis(100));
+                    continue;
+                }
                 let gap_y = rng.range(10, 40);
-                let size = rng.range(10, 20);
+                // 分数越高间隙越窄，但最窄保留 2 以保证可过
+                let score = *thread_score.lock().unwrap();
+                let size = i32::max(2, 20 - score);
                 if sender.send(Obstacle { x, gap_y, size }).is_err() {  // 发送新障碍物到通道
                     break;
                 }
@@ -173,6 +232,24 @@ impl State {
             score: 0,
             obstacle_receiver: receiver,
             high_score,
+            spawner_paused,
+            shared_score,
+            audio: AudioSystem::new(),
+            ground_offset: 0,
+            background_offset: 0,
+        }
+    }
+
+    // 渲染滚动地面及远景视差层，二者循环平铺以保持画面连续
+    fn render_scrolling_background(&self, ctx: &mut BTerm) {
+        for x in 0..SCREEN_WIDTH {
+            if (x + self.background_offset) % BACKGROUND_SPACING == 0 {
+                ctx.set(x, BACKGROUND_ROW, GRAY, LIGHT_BLUE, to_cp437('.'));
+            }
+        }
+        for x in 0..SCREEN_WIDTH {
+            let glyph = GROUND_PATTERN[(x + self.ground_offset) as usize % GROUND_PATTERN.len()];
+            ctx.set(x, GROUND_ROW, GREEN, BLACK, to_cp437(glyph));
         }
     }
 
@@ -194,24 +271,49 @@ impl State {
 
     // 游戏主逻辑
     fn play(&mut self, ctx: &mut BTerm) {
+        // P 键在游戏中/暂停间切换
+        if let Some(VirtualKeyCode::P) = ctx.key {
+            self.toggle_pause();
+        }
+
         ctx.cls_bg(LIGHT_BLUE);  // 蓝色背景
+
+        if matches!(self.mode, GameMode::Paused) {
+            // 暂停时只渲染上一帧画面，不推进计时、移动、滚动或计分
+            self.render_scrolling_background(ctx);
+            self.player.render(ctx);
+            for obstacle in &mut self.obstacles {
+                obstacle.render(ctx, self.player.x);
+            }
+            ctx.print(0, 1, &format!("Score: {}", self.score));
+            ctx.print_centered(25, "PAUSED");
+            return;
+        }
+
         self.frame_time += ctx.frame_time_ms;
-        
+
         // 帧定时器控制
         if self.frame_time > FRAME_DURATION {
             self.frame_time = 0.0;
             if self.player.try_move().is_none() {
                 self.mode = GameMode::End;
             }
+
+            // 地面滚动速度与玩家前进速度一致，远景只推进地面的一部分，形成视差
+            self.ground_offset = (self.ground_offset + 1) % GROUND_PATTERN.len() as i32;
+            if self.ground_offset % 4 == 0 {
+                self.background_offset = (self.background_offset + 1) % BACKGROUND_SPACING;
+            }
         }
-        
+
+        self.render_scrolling_background(ctx);
+
         // 空格键拍打翅膀向上飞
         if let Some(VirtualKeyCode::Space) = ctx.key {
-            if let Err(e) = self.player.flap() {
-                match e {
-                    PlayerError::AlreadyDead => println!("Player is already dead"),
-                    PlayerError::FallingTooFast => println!("Can't flap while falling too fast"),
-                }
+            match self.player.flap() {
+                Ok(()) => self.audio.play_flap(),
+                Err(PlayerError::AlreadyDead) => println!("Player is already dead"),
+                Err(PlayerError::FallingTooFast) => println!("Can't flap while falling too fast"),
             }
         }
         
@@ -232,6 +334,8 @@ impl State {
             if self.player.x > first_obstacle.x {
                 self.score += 1;
                 self.obstacles.remove(0);
+                *self.shared_score.lock().unwrap() = self.score;
+                self.audio.play_score();
             }
         }
 
@@ -246,10 +350,12 @@ impl State {
         
         if player_dead {
             self.mode = GameMode::End;
+            self.audio.play_death();
             // 更新最高分
             let mut high_score = self.high_score.lock().unwrap();
             if self.score > high_score.score {
                 high_score.score = self.score;
+                save_high_score(high_score.score);
             }
         }
     }
@@ -273,6 +379,14 @@ impl State {
         }
     }
 
+    // 在游戏中与暂停之间切换；生成线程的暂停标志统一由 tick() 根据模式维护
+    fn toggle_pause(&mut self) {
+        self.mode = match self.mode {
+            GameMode::Paused => GameMode::Playing,
+            _ => GameMode::Paused,
+        };
+    }
+
     // 重置游戏状态
     fn restart(&mut self) {
         // 重置玩家状态
@@ -283,11 +397,20 @@ impl State {
         
         // 清空现有障碍物
         self.obstacles.clear();
-        
+
         // 创建新的通道
         let (sender, receiver) = mpsc::channel();
         self.obstacle_receiver = receiver;
-        
+
+        // 重置暂停标志，确保新线程从"运行中"开始
+        *self.spawner_paused.lock().unwrap() = false;
+        // 重置共享分数，难度从头计算
+        *self.shared_score.lock().unwrap() = 0;
+
+        // 重置滚动偏移，新一局从静止画面开始
+        self.ground_offset = 0;
+        self.background_offset = 0;
+
         // 生成初始障碍物
         let mut rng = RandomNumberGenerator::new();
         let mut x = SCREEN_WIDTH;
@@ -300,12 +423,19 @@ impl State {
         
         // 启动新的障碍物生成线程
         let thread_x = x;
+        let thread_paused = self.spawner_paused.clone();
+        let thread_score = self.shared_score.clone();
         thread::spawn(move || {
             let mut rng = RandomNumberGenerator::new();
             let mut x = thread_x;
             loop {
+                if *thread_paused.lock().unwrap() {
+                    thread::sleep(std::time::Duration::from_millis(100));
+                    continue;
+                }
                 let gap_y = rng.range(10, 40);
-                let size = rng.range(10, 20);
+                let score = *thread_score.lock().unwrap();
+                let size = i32::max(2, 20 - score);
                 if sender.send(Obstacle { x, gap_y, size }).is_err() {
                     break;
                 }
@@ -316,6 +446,58 @@ impl State {
     }
 }
 
+// 音效子系统：启动时加载一次音效，按事件播放
+struct AudioSystem {
+    _stream: Option<OutputStream>, // 保持输出流存活，否则声音会立刻被丢弃
+    handle: Option<OutputStreamHandle>,
+    flap_clip: Option<Vec<u8>>,
+    score_clip: Option<Vec<u8>>,
+    death_clip: Option<Vec<u8>>,
+}
+
+impl AudioSystem {
+    // 打开默认音频设备并加载音效；没有音频设备（如无头环境）时优雅降级为静音
+    fn new() -> Self {
+        let (stream, handle) = match OutputStream::try_default() {
+            Ok((stream, handle)) => (Some(stream), Some(handle)),
+            Err(_) => (None, None),
+        };
+
+        AudioSystem {
+            _stream: stream,
+            handle,
+            flap_clip: std::fs::read("resources/flap.wav").ok(),
+            score_clip: std::fs::read("resources/score.wav").ok(),
+            death_clip: std::fs::read("resources/death.wav").ok(),
+        }
+    }
+
+    // 播放一段已加载的音效；设备或片段缺失时静默跳过
+    fn play_clip(&self, clip: &Option<Vec<u8>>) {
+        let (Some(handle), Some(bytes)) = (&self.handle, clip) else {
+            return;
+        };
+        if let Ok(sink) = Sink::try_new(handle) {
+            if let Ok(source) = Decoder::new(Cursor::new(bytes.clone())) {
+                sink.append(source);
+                sink.detach();
+            }
+        }
+    }
+
+    pub fn play_flap(&self) {
+        self.play_clip(&self.flap_clip);
+    }
+
+    pub fn play_score(&self) {
+        self.play_clip(&self.score_clip);
+    }
+
+    pub fn play_death(&self) {
+        self.play_clip(&self.death_clip);
+    }
+}
+
 // 障碍物结构体
 struct Obstacle {
     x: i32,
@@ -349,7 +531,7 @@ impl Obstacle {
 }
 
 fn main() -> BError {
-    // 创建游戏窗口
+    // 创建游戏窗口：沿用内置默认字体，动画只需要 cp437 字形，无需额外精灵表资源
     let context = BTermBuilder::simple80x50()
         .with_title("Flappy Bird")
         .build()?;